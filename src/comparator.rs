@@ -0,0 +1,21 @@
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+
+pub trait Comparator<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrdComparator;
+
+impl<T: Ord> Comparator<T> for OrdComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T> Comparator<T> for Arc<dyn Fn(&T, &T) -> Ordering> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
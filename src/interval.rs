@@ -7,18 +7,22 @@ use num::{CheckedAdd, One};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::comparator::Comparator;
+
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Interval<T: Ord> {
+pub struct Interval<T> {
     pub start: T,
     pub end: T,
 }
 
-impl<T: Ord> Interval<T> {
+impl<T> Interval<T> {
     pub fn new(start: T, end: T) -> Self {
         Interval { start, end }
     }
+}
 
+impl<T: Ord> Interval<T> {
     pub fn is_valid(&self) -> bool {
         self.start < self.end
     }
@@ -38,13 +42,46 @@ impl<T: Ord + Clone> Interval<T> {
     }
 }
 
-impl<T: Ord> PartialEq for Interval<T> {
+impl<T: Clone> Interval<T> {
+    pub(crate) fn is_valid_with<C: Comparator<T>>(&self, cmp: &C) -> bool {
+        cmp.compare(&self.start, &self.end) == Ordering::Less
+    }
+
+    pub(crate) fn intersect_with<C: Comparator<T>>(&self, other: &Self, cmp: &C) -> Option<Self> {
+        let start = if cmp.compare(&self.start, &other.start) == Ordering::Greater {
+            self.start.clone()
+        } else {
+            other.start.clone()
+        };
+        let end = if cmp.compare(&self.end, &other.end) == Ordering::Less {
+            self.end.clone()
+        } else {
+            other.end.clone()
+        };
+
+        let result = Interval::new(start, end);
+        if result.is_valid_with(cmp) {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn cmp_with<C: Comparator<T>>(&self, other: &Self, cmp: &C) -> Ordering {
+        match cmp.compare(&self.start, &other.start) {
+            Ordering::Equal => cmp.compare(&self.end, &other.end),
+            ord => ord,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Interval<T> {
     fn eq(&self, other: &Self) -> bool {
         self.start == other.start && self.end == other.end
     }
 }
 
-impl<T: Ord> Eq for Interval<T> {}
+impl<T: Eq> Eq for Interval<T> {}
 
 impl<T: Ord> PartialOrd for Interval<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
@@ -67,25 +104,25 @@ impl<T: Ord + Display> Display for Interval<T> {
     }
 }
 
-impl<T: Ord + Clone> From<Range<T>> for Interval<T> {
+impl<T: Clone> From<Range<T>> for Interval<T> {
     fn from(range: Range<T>) -> Self {
         Interval::new(range.start.clone(), range.end.clone())
     }
 }
 
-impl<T: Ord + Clone> From<&Range<T>> for Interval<T> {
+impl<T: Clone> From<&Range<T>> for Interval<T> {
     fn from(range: &Range<T>) -> Self {
         Interval::new(range.start.clone(), range.end.clone())
     }
 }
 
-impl<T: Ord + Clone + CheckedAdd + One> From<RangeInclusive<T>> for Interval<T> {
+impl<T: Clone + CheckedAdd + One> From<RangeInclusive<T>> for Interval<T> {
     fn from(range: RangeInclusive<T>) -> Self {
         Interval::new(range.start().clone(), range.end().clone() + T::one())
     }
 }
 
-impl<T: Ord + Clone + CheckedAdd + One> From<&RangeInclusive<T>> for Interval<T> {
+impl<T: Clone + CheckedAdd + One> From<&RangeInclusive<T>> for Interval<T> {
     fn from(range: &RangeInclusive<T>) -> Self {
         Interval::new(range.start().clone(), range.end().clone() + T::one())
     }
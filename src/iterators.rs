@@ -1,22 +1,40 @@
 use alloc::vec::Vec;
-use core::{cmp::Ord, fmt::Debug};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
 
+use crate::comparator::{Comparator, OrdComparator};
 use crate::interval::Interval;
 use crate::node::Node;
+use crate::summary::{NoSummary, Summarize};
 
 #[derive(PartialEq, Eq, Debug)]
-pub struct Entry<'a, T: Ord, V> {
+pub struct Entry<'a, T, V> {
     pub value: &'a V,
     pub interval: &'a Interval<T>,
 }
 
-#[derive(Debug)]
-pub struct IntervalTreeIterator<'a, T: Ord + Clone, V> {
-    pub(crate) nodes: Vec<&'a Node<T, V>>,
+pub struct IntervalTreeIterator<'a, T: Clone, V, S: Summarize<V> = NoSummary, C = OrdComparator> {
+    pub(crate) nodes: Vec<&'a Node<T, V, S>>,
     pub(crate) interval: Interval<T>,
+    pub(crate) cmp: C,
 }
 
-impl<'a, T: Ord + Copy + Debug + 'a, V: 'a> Iterator for IntervalTreeIterator<'a, T, V> {
+// `C` has no `Debug` bound (`with_comparator`'s `Arc`-wrapped closure can't implement it), so `cmp` is
+// left out of this impl rather than requiring one.
+impl<T: Clone + Debug, V: Debug, S: Summarize<V> + Debug, C> Debug
+    for IntervalTreeIterator<'_, T, V, S, C>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntervalTreeIterator")
+            .field("nodes", &self.nodes)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: Clone, V: 'a, S: Summarize<V> + 'a, C: Comparator<T>> Iterator
+    for IntervalTreeIterator<'a, T, V, S, C>
+{
     type Item = Entry<'a, T, V>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -26,17 +44,21 @@ impl<'a, T: Ord + Copy + Debug + 'a, V: 'a> Iterator for IntervalTreeIterator<'a
                 Some(node) => node,
             };
 
-            if self.interval.start < cur.max {
+            if self.cmp.compare(&self.interval.start, &cur.max) == Ordering::Less {
                 if let Some(left) = &cur.left_child {
                     self.nodes.push(left);
                 }
 
-                if self.interval.end > cur.interval.start {
+                if self.cmp.compare(&self.interval.end, &cur.interval.start) == Ordering::Greater {
                     if let Some(right) = &cur.right_child {
                         self.nodes.push(right);
                     }
 
-                    if cur.interval.intersect(&self.interval).is_some() {
+                    if cur
+                        .interval
+                        .intersect_with(&self.interval, &self.cmp)
+                        .is_some()
+                    {
                         return Some(Entry {
                             value: cur.value.as_ref().unwrap(),
                             interval: &cur.interval,
@@ -49,18 +71,32 @@ impl<'a, T: Ord + Copy + Debug + 'a, V: 'a> Iterator for IntervalTreeIterator<'a
 }
 
 #[derive(PartialEq, Eq, Debug)]
-pub struct EntryMut<'a, T: Ord, V> {
+pub struct EntryMut<'a, T, V> {
     pub value: &'a mut V,
     pub interval: &'a Interval<T>,
 }
 
-#[derive(Debug)]
-pub struct IntervalTreeIteratorMut<'a, T: Ord + Clone, V> {
-    pub(crate) nodes: Vec<&'a mut Node<T, V>>,
+pub struct IntervalTreeIteratorMut<'a, T: Clone, V, S: Summarize<V> = NoSummary, C = OrdComparator>
+{
+    pub(crate) nodes: Vec<&'a mut Node<T, V, S>>,
     pub(crate) interval: Interval<T>,
+    pub(crate) cmp: C,
 }
 
-impl<'a, T: Ord + Copy + 'a, V: 'a> Iterator for IntervalTreeIteratorMut<'a, T, V> {
+impl<T: Clone + Debug, V: Debug, S: Summarize<V> + Debug, C> Debug
+    for IntervalTreeIteratorMut<'_, T, V, S, C>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntervalTreeIteratorMut")
+            .field("nodes", &self.nodes)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: Clone, V: 'a, S: Summarize<V> + 'a, C: Comparator<T>> Iterator
+    for IntervalTreeIteratorMut<'a, T, V, S, C>
+{
     type Item = EntryMut<'a, T, V>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -70,17 +106,21 @@ impl<'a, T: Ord + Copy + 'a, V: 'a> Iterator for IntervalTreeIteratorMut<'a, T,
                 Some(node) => node,
             };
 
-            if self.interval.start < cur.max {
+            if self.cmp.compare(&self.interval.start, &cur.max) == Ordering::Less {
                 if let Some(left) = &mut cur.left_child {
                     self.nodes.push(left);
                 }
 
-                if self.interval.end > cur.interval.start {
+                if self.cmp.compare(&self.interval.end, &cur.interval.start) == Ordering::Greater {
                     if let Some(right) = &mut cur.right_child {
                         self.nodes.push(right);
                     }
 
-                    if cur.interval.intersect(&self.interval).is_some() {
+                    if cur
+                        .interval
+                        .intersect_with(&self.interval, &self.cmp)
+                        .is_some()
+                    {
                         return Some(EntryMut {
                             value: cur.value.as_mut().unwrap(),
                             interval: &cur.interval,
@@ -91,3 +131,89 @@ impl<'a, T: Ord + Copy + 'a, V: 'a> Iterator for IntervalTreeIteratorMut<'a, T,
         }
     }
 }
+
+pub struct Gaps<'a, T: Clone, V, S: Summarize<V> = NoSummary, C = OrdComparator> {
+    pub(crate) nodes: Vec<&'a Node<T, V, S>>,
+    pub(crate) range: Interval<T>,
+    pub(crate) cmp: C,
+    pub(crate) cursor: T,
+    pub(crate) done: bool,
+}
+
+impl<T: Clone + Debug, V: Debug, S: Summarize<V> + Debug, C> Debug for Gaps<'_, T, V, S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Gaps")
+            .field("nodes", &self.nodes)
+            .field("range", &self.range)
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: Clone, V, S: Summarize<V>, C: Comparator<T>> Gaps<'a, T, V, S, C> {
+    pub(crate) fn push_left_spine(&mut self, mut node: Option<&'a Node<T, V, S>>) {
+        while let Some(n) = node {
+            if self.cmp.compare(&self.range.start, &n.max) != Ordering::Less {
+                break;
+            }
+            self.nodes.push(n);
+            node = n.left_child.as_deref();
+        }
+    }
+}
+
+impl<'a, T: Clone, V: 'a, S: Summarize<V> + 'a, C: Comparator<T>> Iterator
+    for Gaps<'a, T, V, S, C>
+{
+    type Item = Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.nodes.pop() {
+            if self.cmp.compare(&self.range.end, &node.interval.start) != Ordering::Greater {
+                // Every remaining node in ascending order starts at or after `range.end`, so
+                // none of them can intersect `range` either; stop early.
+                self.nodes.clear();
+                break;
+            }
+
+            self.push_left_spine(node.right_child.as_deref());
+
+            if node
+                .interval
+                .intersect_with(&self.range, &self.cmp)
+                .is_none()
+            {
+                continue;
+            }
+
+            let gap = if self.cmp.compare(&self.cursor, &node.interval.start) == Ordering::Less {
+                let end =
+                    if self.cmp.compare(&node.interval.start, &self.range.end) == Ordering::Less {
+                        node.interval.start.clone()
+                    } else {
+                        self.range.end.clone()
+                    };
+                Some(Interval::new(self.cursor.clone(), end))
+            } else {
+                None
+            };
+
+            if self.cmp.compare(&node.interval.end, &self.cursor) == Ordering::Greater {
+                self.cursor = node.interval.end.clone();
+            }
+
+            if gap.is_some() {
+                return gap;
+            }
+        }
+
+        if !self.done {
+            self.done = true;
+            if self.cmp.compare(&self.cursor, &self.range.end) == Ordering::Less {
+                return Some(Interval::new(self.cursor.clone(), self.range.end.clone()));
+            }
+        }
+
+        None
+    }
+}
@@ -59,8 +59,11 @@
 pub extern crate alloc;
 
 use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::{
     cmp::{Ord, Ordering},
+    fmt::{self, Debug},
     ops::Range,
 };
 #[cfg(feature = "serde")]
@@ -73,20 +76,69 @@ mod interval;
 pub use interval::Interval;
 
 mod iterators;
-pub use iterators::{Entry, EntryMut, IntervalTreeIterator, IntervalTreeIteratorMut};
+pub use iterators::{Entry, EntryMut, Gaps, IntervalTreeIterator, IntervalTreeIteratorMut};
+
+mod summary;
+pub use summary::{NoSummary, Summarize};
+
+mod comparator;
+pub use comparator::{Comparator, OrdComparator};
 
-#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct IntervalTree<T: Ord + Clone, V> {
-    root: Option<Box<Node<T, V>>>,
+pub struct IntervalTree<T: Clone, V, S: Summarize<V> = NoSummary, C = OrdComparator> {
+    root: Option<Box<Node<T, V, S>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cmp: C,
 }
 
-impl<T: Ord + Clone, V> IntervalTree<T, V> {
+// `cmp` isn't necessarily `Debug` (e.g. the `Arc`-wrapped closure used by `with_comparator` isn't),
+// so it's omitted here.
+impl<T: Clone + Debug, V: Debug, S: Summarize<V> + Debug, C> Debug for IntervalTree<T, V, S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntervalTree")
+            .field("root", &self.root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone, V: Clone, S: Summarize<V> + Clone, C: Clone> Clone for IntervalTree<T, V, S, C> {
+    fn clone(&self) -> Self {
+        IntervalTree {
+            root: self.root.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<T: Ord + Clone, V, S: Summarize<V>> IntervalTree<T, V, S, OrdComparator> {
     #[must_use]
     pub fn new() -> Self {
-        IntervalTree { root: None }
+        IntervalTree {
+            root: None,
+            cmp: OrdComparator,
+        }
+    }
+}
+
+impl<T: Ord + Clone, V, S: Summarize<V>> Default for IntervalTree<T, V, S, OrdComparator> {
+    fn default() -> Self {
+        IntervalTree::new()
     }
+}
 
+impl<T: Clone + 'static, V, S: Summarize<V>>
+    IntervalTree<T, V, S, Arc<dyn Fn(&T, &T) -> Ordering>>
+{
+    #[must_use]
+    pub fn with_comparator<F: Fn(&T, &T) -> Ordering + 'static>(cmp: F) -> Self {
+        IntervalTree {
+            root: None,
+            cmp: Arc::new(cmp),
+        }
+    }
+}
+
+impl<T: Clone, V, S: Summarize<V>, C: Comparator<T>> IntervalTree<T, V, S, C> {
     #[must_use]
     pub fn is_empty(&self) -> bool {
         self.root.is_none()
@@ -103,17 +155,22 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
     }
 
     #[must_use]
-    pub fn query<I: Into<Interval<T>>>(&self, interval: I) -> IntervalTreeIterator<'_, T, V> {
+    pub fn query<I: Into<Interval<T>>>(&self, interval: I) -> IntervalTreeIterator<'_, T, V, S, C>
+    where
+        C: Clone,
+    {
         if let Some(ref n) = self.root {
             IntervalTreeIterator {
                 nodes: vec![n],
                 interval: interval.into(),
+                cmp: self.cmp.clone(),
             }
         } else {
             let nodes = vec![];
             IntervalTreeIterator {
                 nodes,
                 interval: interval.into(),
+                cmp: self.cmp.clone(),
             }
         }
     }
@@ -122,21 +179,82 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
     pub fn query_mut<I: Into<Interval<T>>>(
         &mut self,
         interval: I,
-    ) -> IntervalTreeIteratorMut<'_, T, V> {
+    ) -> IntervalTreeIteratorMut<'_, T, V, S, C>
+    where
+        C: Clone,
+    {
         if let Some(ref mut n) = self.root {
             IntervalTreeIteratorMut {
                 nodes: vec![n],
                 interval: interval.into(),
+                cmp: self.cmp.clone(),
             }
         } else {
             let nodes = vec![];
             IntervalTreeIteratorMut {
                 nodes,
                 interval: interval.into(),
+                cmp: self.cmp.clone(),
             }
         }
     }
 
+    #[must_use]
+    pub fn summary(&self) -> S::Summary {
+        match &self.root {
+            Some(node) => node.summary.clone(),
+            None => S::unit(),
+        }
+    }
+
+    #[must_use]
+    pub fn summary_over<I: Into<Interval<T>>>(&self, range: I) -> S::Summary {
+        let range = range.into();
+        let mut acc = S::unit();
+        let mut nodes = match &self.root {
+            Some(n) => vec![n.as_ref()],
+            None => vec![],
+        };
+
+        while let Some(cur) = nodes.pop() {
+            if self.cmp.compare(&range.start, &cur.max) == Ordering::Less {
+                if let Some(left) = &cur.left_child {
+                    nodes.push(left);
+                }
+
+                if self.cmp.compare(&range.end, &cur.interval.start) == Ordering::Greater {
+                    if let Some(right) = &cur.right_child {
+                        nodes.push(right);
+                    }
+
+                    if cur.interval.intersect_with(&range, &self.cmp).is_some() {
+                        acc = S::combine(&acc, &S::lift(cur.value.as_ref().unwrap()));
+                    }
+                }
+            }
+        }
+
+        acc
+    }
+
+    #[must_use]
+    pub fn gaps<I: Into<Interval<T>>>(&self, range: I) -> Gaps<'_, T, V, S, C>
+    where
+        C: Clone,
+    {
+        let range = range.into();
+        let cursor = range.start.clone();
+        let mut gaps = Gaps {
+            nodes: vec![],
+            range,
+            cmp: self.cmp.clone(),
+            cursor,
+            done: false,
+        };
+        gaps.push_left_spine(self.root.as_deref());
+        gaps
+    }
+
     pub fn insert<I: Into<Interval<T>>>(&mut self, interval: I, value: V) {
         let interval = interval.into();
         let max = interval.end.clone();
@@ -146,29 +264,32 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
             interval,
             value,
             max,
+            &self.cmp,
         ));
     }
 
     #[allow(clippy::unnecessary_box_returns)]
     fn insert_helper(
-        node: Option<Box<Node<T, V>>>,
+        node: Option<Box<Node<T, V, S>>>,
         interval: Interval<T>,
         value: V,
         max: T,
-    ) -> Box<Node<T, V>> {
+        cmp: &C,
+    ) -> Box<Node<T, V, S>> {
         if node.is_none() {
             return Box::new(Node::new(interval, value, max, 0, 1));
         }
 
         let mut node_ref = node.unwrap();
 
-        match interval.cmp(&node_ref.interval) {
+        match interval.cmp_with(&node_ref.interval, cmp) {
             Ordering::Less => {
                 node_ref.left_child = Some(IntervalTree::insert_helper(
                     node_ref.left_child,
                     interval,
                     value,
                     max,
+                    cmp,
                 ));
             }
             Ordering::Greater => {
@@ -177,63 +298,69 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
                     interval,
                     value,
                     max,
+                    cmp,
                 ));
             }
-            Ordering::Equal => return node_ref,
+            Ordering::Equal => node_ref.value = Some(value),
         }
 
         node_ref.update_height();
         node_ref.update_size();
-        node_ref.update_max();
+        node_ref.update_max(cmp);
+        node_ref.update_summary();
 
-        IntervalTree::balance(node_ref)
+        IntervalTree::balance(node_ref, cmp)
     }
 
     #[allow(clippy::unnecessary_box_returns)]
-    fn balance(mut node: Box<Node<T, V>>) -> Box<Node<T, V>> {
+    fn balance(mut node: Box<Node<T, V, S>>, cmp: &C) -> Box<Node<T, V, S>> {
         if Node::balance_factor(&node) < -1 {
             if Node::balance_factor(node.right_child.as_ref().unwrap()) > 0 {
-                node.right_child = Some(IntervalTree::rotate_right(node.right_child.unwrap()));
+                node.right_child = Some(IntervalTree::rotate_right(node.right_child.unwrap(), cmp));
             }
-            node = IntervalTree::rotate_left(node);
+            node = IntervalTree::rotate_left(node, cmp);
         } else if Node::balance_factor(&node) > 1 {
             if Node::balance_factor(node.left_child.as_ref().unwrap()) < 0 {
-                node.left_child = Some(IntervalTree::rotate_left(node.left_child.unwrap()));
+                node.left_child = Some(IntervalTree::rotate_left(node.left_child.unwrap(), cmp));
             }
-            node = IntervalTree::rotate_right(node);
+            node = IntervalTree::rotate_right(node, cmp);
         }
         node
     }
 
     #[allow(clippy::unnecessary_box_returns)]
-    fn rotate_right(mut node: Box<Node<T, V>>) -> Box<Node<T, V>> {
+    fn rotate_right(mut node: Box<Node<T, V, S>>, cmp: &C) -> Box<Node<T, V, S>> {
         let mut y = node.left_child.unwrap();
         node.left_child = y.right_child;
         y.size = node.size;
         node.update_height();
         node.update_size();
-        node.update_max();
+        node.update_max(cmp);
+        node.update_summary();
 
         y.right_child = Some(node);
         y.update_height();
-        y.update_max();
+        y.update_max(cmp);
+        y.update_summary();
 
         y
     }
 
     #[allow(clippy::unnecessary_box_returns)]
-    fn rotate_left(mut node: Box<Node<T, V>>) -> Box<Node<T, V>> {
+    fn rotate_left(mut node: Box<Node<T, V, S>>, cmp: &C) -> Box<Node<T, V, S>> {
         let mut y = node.right_child.unwrap();
         node.right_child = y.left_child;
         y.size = node.size;
 
         node.update_height();
         node.update_size();
-        node.update_max();
+        node.update_max(cmp);
+        node.update_summary();
 
         y.left_child = Some(node);
         y.update_height();
-        y.update_max();
+        y.update_max(cmp);
+        y.update_summary();
 
         y
     }
@@ -241,43 +368,49 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
     pub fn delete<I: Into<Interval<T>>>(&mut self, interval: I) {
         if !self.is_empty() {
             let interval = interval.into();
-            self.root = IntervalTree::delete_helper(self.root.take(), &interval);
+            self.root = IntervalTree::delete_helper(self.root.take(), &interval, &self.cmp);
         }
     }
 
     fn delete_helper(
-        node: Option<Box<Node<T, V>>>,
+        node: Option<Box<Node<T, V, S>>>,
         interval: &Interval<T>,
-    ) -> Option<Box<Node<T, V>>> {
+        cmp: &C,
+    ) -> Option<Box<Node<T, V, S>>> {
         match node {
             None => None,
             Some(mut node) => {
-                if *interval < node.interval {
-                    node.left_child = IntervalTree::delete_helper(node.left_child.take(), interval);
-                } else if *interval > node.interval {
-                    node.right_child =
-                        IntervalTree::delete_helper(node.right_child.take(), interval);
-                } else if node.left_child.is_none() {
-                    return node.right_child;
-                } else if node.right_child.is_none() {
-                    return node.left_child;
-                } else {
-                    let mut y = node;
-                    node = IntervalTree::min(&mut y.right_child);
-                    node.right_child = IntervalTree::delete_min_helper(y.right_child.unwrap());
-                    node.left_child = y.left_child;
+                match interval.cmp_with(&node.interval, cmp) {
+                    Ordering::Less => {
+                        node.left_child =
+                            IntervalTree::delete_helper(node.left_child.take(), interval, cmp);
+                    }
+                    Ordering::Greater => {
+                        node.right_child =
+                            IntervalTree::delete_helper(node.right_child.take(), interval, cmp);
+                    }
+                    Ordering::Equal if node.left_child.is_none() => return node.right_child,
+                    Ordering::Equal if node.right_child.is_none() => return node.left_child,
+                    Ordering::Equal => {
+                        let mut y = node;
+                        node = IntervalTree::<T, V, S, C>::min(&mut y.right_child);
+                        node.right_child =
+                            IntervalTree::delete_min_helper(y.right_child.unwrap(), cmp);
+                        node.left_child = y.left_child;
+                    }
                 }
 
                 node.update_height();
                 node.update_size();
-                node.update_max();
-                Some(IntervalTree::balance(node))
+                node.update_max(cmp);
+                node.update_summary();
+                Some(IntervalTree::balance(node, cmp))
             }
         }
     }
 
     #[allow(clippy::unnecessary_box_returns)]
-    fn min(node: &mut Option<Box<Node<T, V>>>) -> Box<Node<T, V>> {
+    fn min(node: &mut Option<Box<Node<T, V, S>>>) -> Box<Node<T, V, S>> {
         match node {
             Some(node) => {
                 if node.left_child.is_none() {
@@ -289,7 +422,7 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
                         1,
                     ))
                 } else {
-                    IntervalTree::min(&mut node.left_child)
+                    IntervalTree::<T, V, S, C>::min(&mut node.left_child)
                 }
             }
             None => panic!("Called min on None node"),
@@ -298,50 +431,298 @@ impl<T: Ord + Clone, V> IntervalTree<T, V> {
 
     pub fn delete_min(&mut self) {
         if !self.is_empty() {
-            self.root = IntervalTree::delete_min_helper(self.root.take().unwrap());
+            self.root = IntervalTree::delete_min_helper(self.root.take().unwrap(), &self.cmp);
         }
     }
 
-    fn delete_min_helper(mut node: Box<Node<T, V>>) -> Option<Box<Node<T, V>>> {
+    fn delete_min_helper(mut node: Box<Node<T, V, S>>, cmp: &C) -> Option<Box<Node<T, V, S>>> {
         if node.left_child.is_none() {
             return node.right_child.take();
         }
 
-        node.left_child = IntervalTree::delete_min_helper(node.left_child.unwrap());
+        node.left_child = IntervalTree::delete_min_helper(node.left_child.unwrap(), cmp);
 
         node.update_height();
         node.update_size();
-        node.update_max();
+        node.update_max(cmp);
+        node.update_summary();
 
-        Some(IntervalTree::balance(node))
+        Some(IntervalTree::balance(node, cmp))
     }
 
     pub fn delete_max(&mut self) {
         if !self.is_empty() {
-            self.root = IntervalTree::delete_max_helper(self.root.take().unwrap());
+            self.root = IntervalTree::delete_max_helper(self.root.take().unwrap(), &self.cmp);
         }
     }
 
-    fn delete_max_helper(mut node: Box<Node<T, V>>) -> Option<Box<Node<T, V>>> {
+    fn delete_max_helper(mut node: Box<Node<T, V, S>>, cmp: &C) -> Option<Box<Node<T, V, S>>> {
         if node.right_child.is_none() {
             return node.left_child.take();
         }
 
-        node.right_child = IntervalTree::delete_max_helper(node.right_child.unwrap());
+        node.right_child = IntervalTree::delete_max_helper(node.right_child.unwrap(), cmp);
 
         node.update_height();
         node.update_size();
-        node.update_max();
+        node.update_max(cmp);
+        node.update_summary();
 
-        Some(IntervalTree::balance(node))
+        Some(IntervalTree::balance(node, cmp))
     }
 
     pub fn clear(&mut self) {
         self.root = None;
     }
+
+    pub fn insert_overwrite<I: Into<Interval<T>>>(&mut self, range: I, value: V)
+    where
+        V: Clone,
+    {
+        let range = range.into();
+        let mut overlapping = vec![];
+        IntervalTree::collect_overlapping(
+            self.root.as_deref(),
+            &range,
+            &self.cmp,
+            &mut overlapping,
+        );
+
+        for (old_interval, old_value) in overlapping {
+            self.delete(old_interval.clone());
+
+            if self.cmp.compare(&old_interval.start, &range.start) == Ordering::Less {
+                self.insert(
+                    Interval::new(old_interval.start.clone(), range.start.clone()),
+                    old_value.clone(),
+                );
+            }
+            if self.cmp.compare(&range.end, &old_interval.end) == Ordering::Less {
+                self.insert(
+                    Interval::new(range.end.clone(), old_interval.end.clone()),
+                    old_value,
+                );
+            }
+        }
+
+        self.insert(range, value);
+    }
+
+    fn collect_overlapping(
+        node: Option<&Node<T, V, S>>,
+        range: &Interval<T>,
+        cmp: &C,
+        out: &mut Vec<(Interval<T>, V)>,
+    ) where
+        V: Clone,
+    {
+        let Some(node) = node else { return };
+
+        if cmp.compare(&range.start, &node.max) == Ordering::Less {
+            IntervalTree::collect_overlapping(node.left_child.as_deref(), range, cmp, out);
+
+            if cmp.compare(&range.end, &node.interval.start) == Ordering::Greater {
+                if node.interval.intersect_with(range, cmp).is_some() {
+                    out.push((node.interval.clone(), node.value.as_ref().unwrap().clone()));
+                }
+
+                IntervalTree::collect_overlapping(node.right_child.as_deref(), range, cmp, out);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn select(&self, k: usize) -> Option<Entry<'_, T, V>> {
+        IntervalTree::<T, V, S, C>::select_helper(self.root.as_deref(), k)
+    }
+
+    fn select_helper(node: Option<&Node<T, V, S>>, k: usize) -> Option<Entry<'_, T, V>> {
+        let node = node?;
+        let l = Node::size(&node.left_child);
+        match k.cmp(&l) {
+            Ordering::Less => {
+                IntervalTree::<T, V, S, C>::select_helper(node.left_child.as_deref(), k)
+            }
+            Ordering::Equal => Some(Entry {
+                value: node.value.as_ref().unwrap(),
+                interval: &node.interval,
+            }),
+            Ordering::Greater => {
+                IntervalTree::<T, V, S, C>::select_helper(node.right_child.as_deref(), k - l - 1)
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn rank<I: Into<Interval<T>>>(&self, interval: I) -> usize {
+        IntervalTree::rank_helper(self.root.as_deref(), &interval.into(), &self.cmp)
+    }
+
+    fn rank_helper(node: Option<&Node<T, V, S>>, interval: &Interval<T>, cmp: &C) -> usize {
+        match node {
+            None => 0,
+            Some(node) => match interval.cmp_with(&node.interval, cmp) {
+                Ordering::Less => {
+                    IntervalTree::rank_helper(node.left_child.as_deref(), interval, cmp)
+                }
+                Ordering::Equal => Node::size(&node.left_child),
+                Ordering::Greater => {
+                    Node::size(&node.left_child)
+                        + 1
+                        + IntervalTree::rank_helper(node.right_child.as_deref(), interval, cmp)
+                }
+            },
+        }
+    }
+
+    pub fn remove_by_rank(&mut self, k: usize) -> Option<(Interval<T>, V)> {
+        let (root, removed) = IntervalTree::remove_by_rank_helper(self.root.take(), k, &self.cmp);
+        self.root = root;
+        removed
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn remove_by_rank_helper(
+        node: Option<Box<Node<T, V, S>>>,
+        k: usize,
+        cmp: &C,
+    ) -> (Option<Box<Node<T, V, S>>>, Option<(Interval<T>, V)>) {
+        match node {
+            None => (None, None),
+            Some(mut node) => {
+                let l = Node::size(&node.left_child);
+                let removed;
+                match k.cmp(&l) {
+                    Ordering::Less => {
+                        let (left, r) =
+                            IntervalTree::remove_by_rank_helper(node.left_child.take(), k, cmp);
+                        node.left_child = left;
+                        removed = r;
+                    }
+                    Ordering::Greater => {
+                        let (right, r) = IntervalTree::remove_by_rank_helper(
+                            node.right_child.take(),
+                            k - l - 1,
+                            cmp,
+                        );
+                        node.right_child = right;
+                        removed = r;
+                    }
+                    Ordering::Equal => {
+                        removed = Some((node.interval.clone(), node.value.take().unwrap()));
+                        if node.left_child.is_none() {
+                            return (node.right_child.take(), removed);
+                        } else if node.right_child.is_none() {
+                            return (node.left_child.take(), removed);
+                        }
+
+                        let mut y = node;
+                        node = IntervalTree::<T, V, S, C>::min(&mut y.right_child);
+                        node.right_child =
+                            IntervalTree::delete_min_helper(y.right_child.unwrap(), cmp);
+                        node.left_child = y.left_child;
+                    }
+                }
+
+                node.update_height();
+                node.update_size();
+                node.update_max(cmp);
+                node.update_summary();
+                (Some(IntervalTree::balance(node, cmp)), removed)
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn split_off(&mut self, at: T) -> IntervalTree<T, V, S, C>
+    where
+        V: Clone,
+        C: Clone,
+    {
+        let mut kept = vec![];
+        let mut moved = vec![];
+        IntervalTree::partition_by_start(self.root.take(), &at, &self.cmp, &mut kept, &mut moved);
+
+        for (interval, value) in kept {
+            self.insert(interval, value);
+        }
+
+        let mut right = IntervalTree {
+            root: None,
+            cmp: self.cmp.clone(),
+        };
+        for (interval, value) in moved {
+            right.insert(interval, value);
+        }
+
+        right
+    }
+
+    fn partition_by_start(
+        node: Option<Box<Node<T, V, S>>>,
+        at: &T,
+        cmp: &C,
+        kept: &mut Vec<(Interval<T>, V)>,
+        moved: &mut Vec<(Interval<T>, V)>,
+    ) where
+        V: Clone,
+    {
+        let Some(node) = node else { return };
+        let Node {
+            interval,
+            value,
+            left_child,
+            right_child,
+            ..
+        } = *node;
+
+        IntervalTree::partition_by_start(left_child, at, cmp, kept, moved);
+
+        let value = value.unwrap();
+        let starts_before_at = cmp.compare(&interval.start, at) == Ordering::Less;
+        let ends_after_at = cmp.compare(at, &interval.end) == Ordering::Less;
+        match (starts_before_at, ends_after_at) {
+            (true, true) => {
+                kept.push((
+                    Interval::new(interval.start.clone(), at.clone()),
+                    value.clone(),
+                ));
+                moved.push((Interval::new(at.clone(), interval.end), value));
+            }
+            (true, false) => kept.push((interval, value)),
+            (false, _) => moved.push((interval, value)),
+        }
+
+        IntervalTree::partition_by_start(right_child, at, cmp, kept, moved);
+    }
+
+    pub fn append(&mut self, other: IntervalTree<T, V, S, C>) {
+        let mut entries = vec![];
+        IntervalTree::<T, V, S, C>::drain_into(other.root, &mut entries);
+        for (interval, value) in entries {
+            self.insert(interval, value);
+        }
+    }
+
+    fn drain_into(node: Option<Box<Node<T, V, S>>>, out: &mut Vec<(Interval<T>, V)>) {
+        let Some(node) = node else { return };
+        let Node {
+            interval,
+            value,
+            left_child,
+            right_child,
+            ..
+        } = *node;
+
+        IntervalTree::<T, V, S, C>::drain_into(left_child, out);
+        out.push((interval, value.unwrap()));
+        IntervalTree::<T, V, S, C>::drain_into(right_child, out);
+    }
 }
 
-impl<T: Ord + Clone, V> FromIterator<(Range<T>, V)> for IntervalTree<T, V> {
+impl<T: Ord + Clone, V, S: Summarize<V>> FromIterator<(Range<T>, V)>
+    for IntervalTree<T, V, S, OrdComparator>
+{
     fn from_iter<I: IntoIterator<Item = (Range<T>, V)>>(iter: I) -> Self {
         let mut ret = IntervalTree::new();
         for (interval, value) in iter {
@@ -432,4 +813,267 @@ mod tests {
         let tree: IntervalTree<u32, u32> = IntervalTree::new();
         verify(&tree, 42, &[]);
     }
+
+    #[test]
+    fn select_and_rank() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        for i in 0..20 {
+            tree.insert((i * 3)..(i * 3 + 2), i);
+        }
+
+        for k in 0..tree.size() {
+            let entry = tree.select(k).unwrap();
+            assert_eq!(tree.rank(*entry.interval), k);
+        }
+
+        assert!(tree.select(tree.size()).is_none());
+
+        let first = *tree.select(0).unwrap().interval;
+        assert_eq!(tree.rank(first), 0);
+    }
+
+    #[test]
+    fn remove_by_rank_preserves_order() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        for i in 0..10 {
+            tree.insert((i * 10)..(i * 10 + 5), i);
+        }
+
+        let (interval, value) = tree.remove_by_rank(3).unwrap();
+        assert_eq!(interval, Interval::new(30, 35));
+        assert_eq!(value, 3);
+        assert_eq!(tree.size(), 9);
+
+        let values: Vec<_> = (0..tree.size())
+            .map(|k| *tree.select(k).unwrap().value)
+            .collect();
+        assert_eq!(values, [0, 1, 2, 4, 5, 6, 7, 8, 9]);
+
+        assert!(tree.remove_by_rank(tree.size()).is_none());
+    }
+
+    struct TotalBytes;
+
+    impl Summarize<usize> for TotalBytes {
+        type Summary = usize;
+
+        fn unit() -> Self::Summary {
+            0
+        }
+
+        fn lift(value: &usize) -> Self::Summary {
+            *value
+        }
+
+        fn combine(a: &Self::Summary, b: &Self::Summary) -> Self::Summary {
+            a + b
+        }
+    }
+
+    #[test]
+    fn summary_tracks_total_bytes() {
+        let mut tree = IntervalTree::<u32, usize, TotalBytes>::new();
+        tree.insert(0..0x1000, 0x1000);
+        tree.insert(0x2000..0x3000, 0x1000);
+        tree.insert(0x4000..0x4100, 0x100);
+
+        assert_eq!(tree.summary(), 0x2100);
+        assert_eq!(tree.summary_over(0x2000..0x2500), 0x1000);
+        assert_eq!(tree.summary_over(0..0x10000), 0x2100);
+
+        tree.delete(0x2000..0x3000);
+        assert_eq!(tree.summary(), 0x1100);
+    }
+
+    #[test]
+    fn with_comparator_orders_by_reverse_address() {
+        // Every interval's `start` must still precede its `end` *under `cmp`*, so with a
+        // descending comparator the higher bound comes first.
+        let mut tree =
+            IntervalTree::<u32, u32, NoSummary, _>::with_comparator(|a: &u32, b: &u32| b.cmp(a));
+        tree.insert(Interval::new(10, 0), 1);
+        tree.insert(Interval::new(20, 10), 2);
+        tree.insert(Interval::new(30, 20), 3);
+
+        assert_eq!(tree.select(0).unwrap().value, &3);
+        assert_eq!(tree.select(2).unwrap().value, &1);
+        assert_eq!(tree.rank(Interval::new(30, 20)), 0);
+
+        let mut seen: Vec<_> = tree.query(Interval::new(25, 5)).map(|e| *e.value).collect();
+        seen.sort_unstable();
+        assert_eq!(seen, [1, 2, 3]);
+
+        tree.delete(Interval::new(20, 10));
+        assert_eq!(tree.size(), 2);
+    }
+
+    #[test]
+    fn gaps_between_and_around_mappings() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(0x1000..0x2000, 1);
+        tree.insert(0x3000..0x4000, 2);
+        tree.insert(0x4000..0x5000, 3);
+
+        let gaps: Vec<_> = tree.gaps(0..0x6000).map(|g| (g.start, g.end)).collect();
+        assert_eq!(gaps, [(0, 0x1000), (0x2000, 0x3000), (0x5000, 0x6000)]);
+
+        // Touching intervals (`0x4000` is shared by two mappings) must not produce a gap.
+        let gaps: Vec<_> = tree
+            .gaps(0x3000..0x5000)
+            .map(|g| (g.start, g.end))
+            .collect();
+        assert!(gaps.is_empty());
+
+        // A range fully contained in a single mapping yields no gaps.
+        assert!(tree.gaps(0x1100..0x1200).next().is_none());
+
+        // A range that doesn't overlap anything yields itself, whole.
+        let gaps: Vec<_> = tree
+            .gaps(0x6000..0x7000)
+            .map(|g| (g.start, g.end))
+            .collect();
+        assert_eq!(gaps, [(0x6000, 0x7000)]);
+    }
+
+    #[test]
+    fn gaps_over_empty_tree_is_the_whole_range() {
+        let tree = IntervalTree::<u32, u32>::new();
+        let gaps: Vec<_> = tree.gaps(0..100).map(|g| (g.start, g.end)).collect();
+        assert_eq!(gaps, [(0, 100)]);
+    }
+
+    #[test]
+    fn insert_overwrite_clips_overlapping_mappings() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(0..0x1000, 1);
+        tree.insert(0x1000..0x3000, 2);
+        tree.insert(0x5000..0x6000, 3);
+
+        // `0x1000..0x3000` is strictly contained in the new range, so it's removed outright;
+        // `0x5000..0x6000` strictly contains the new range's end, producing one remnant.
+        tree.insert_overwrite(0x500..0x5500, 4);
+
+        let mut entries: Vec<_> = tree
+            .query(0..0x10000)
+            .map(|e| (e.interval.start, e.interval.end, *e.value))
+            .collect();
+        entries.sort_unstable();
+
+        assert_eq!(
+            entries,
+            [(0, 0x500, 1), (0x500, 0x5500, 4), (0x5500, 0x6000, 3),]
+        );
+    }
+
+    #[test]
+    fn insert_overwrite_splits_a_single_containing_mapping() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(0..0x10000, 1);
+
+        tree.insert_overwrite(0x2000..0x3000, 2);
+
+        let mut entries: Vec<_> = tree
+            .query(0..0x10000)
+            .map(|e| (e.interval.start, e.interval.end, *e.value))
+            .collect();
+        entries.sort_unstable();
+
+        assert_eq!(
+            entries,
+            [(0, 0x2000, 1), (0x2000, 0x3000, 2), (0x3000, 0x10000, 1)]
+        );
+    }
+
+    #[test]
+    fn insert_overwrite_remnant_keeps_its_value_on_key_collision() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(8..12, 99);
+        tree.insert(8..20, 2);
+
+        // The clipped left remnant of `8..20` is exactly `8..12`, which already holds an
+        // unrelated mapping; overwriting that key must not silently drop the remnant's value.
+        tree.insert_overwrite(12..20, 9999);
+
+        let mut entries: Vec<_> = tree
+            .query(0..u32::MAX)
+            .map(|e| (e.interval.start, e.interval.end, *e.value))
+            .collect();
+        entries.sort_unstable();
+
+        assert_eq!(entries, [(8, 12, 2), (12, 20, 9999)]);
+    }
+
+    fn entries(tree: &IntervalTree<u32, u32>) -> Vec<(u32, u32, u32)> {
+        let mut entries: Vec<_> = tree
+            .query(0..u32::MAX)
+            .map(|e| (e.interval.start, e.interval.end, *e.value))
+            .collect();
+        entries.sort_unstable();
+        entries
+    }
+
+    #[test]
+    fn split_off_clips_a_straddling_interval() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(0..0x1000, 1);
+        tree.insert(0x1000..0x3000, 2);
+        tree.insert(0x4000..0x5000, 3);
+
+        let right = tree.split_off(0x2000);
+
+        assert_eq!(entries(&tree), [(0, 0x1000, 1), (0x1000, 0x2000, 2)]);
+        assert_eq!(entries(&right), [(0x2000, 0x3000, 2), (0x4000, 0x5000, 3)]);
+    }
+
+    #[test]
+    fn split_off_then_append_round_trips() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        for i in 0..20 {
+            tree.insert((i * 10)..(i * 10 + 5), i);
+        }
+        let original = entries(&tree);
+
+        let mut right = tree.split_off(100);
+        tree.append(right.split_off(0));
+
+        assert_eq!(entries(&tree), original);
+    }
+
+    #[test]
+    fn split_off_remnant_keeps_its_value_on_key_collision() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(107..132, 99);
+        tree.insert(107..150, 2);
+
+        // The clipped left remnant of `107..150` is exactly `107..132`, which already holds an
+        // unrelated mapping; keeping that key must not silently drop the remnant's value.
+        let right = tree.split_off(132);
+
+        assert_eq!(entries(&tree), [(107, 132, 2)]);
+        assert_eq!(entries(&right), [(132, 150, 2)]);
+    }
+
+    #[test]
+    fn append_overwrites_on_key_collision() {
+        let mut a = IntervalTree::<u32, u32>::new();
+        a.insert(10..20, 1);
+        let mut b = IntervalTree::<u32, u32>::new();
+        b.insert(10..20, 2);
+
+        a.append(b);
+
+        assert_eq!(entries(&a), [(10, 20, 2)]);
+    }
+
+    #[test]
+    fn split_off_at_boundary_with_no_straddling_interval() {
+        let mut tree = IntervalTree::<u32, u32>::new();
+        tree.insert(0..10, 1);
+        tree.insert(20..30, 2);
+
+        let right = tree.split_off(15);
+
+        assert_eq!(entries(&tree), [(0, 10, 1)]);
+        assert_eq!(entries(&right), [(20, 30, 2)]);
+    }
 }
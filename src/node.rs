@@ -1,33 +1,38 @@
 use alloc::boxed::Box;
-use core::cmp::{max, Ord};
+use core::cmp::{max, Ordering};
 
+use crate::comparator::Comparator;
 use crate::interval::Interval;
+use crate::summary::Summarize;
 
 #[derive(Clone, Debug)]
-pub(crate) struct Node<T: Ord + Clone, V> {
+pub(crate) struct Node<T: Clone, V, S: Summarize<V>> {
     pub interval: Interval<T>,
     pub value: Option<V>,
     pub max: T,
     pub height: usize,
     pub size: usize,
-    pub left_child: Option<Box<Node<T, V>>>,
-    pub right_child: Option<Box<Node<T, V>>>,
+    pub summary: S::Summary,
+    pub left_child: Option<Box<Node<T, V, S>>>,
+    pub right_child: Option<Box<Node<T, V, S>>>,
 }
 
-impl<T: Ord + Clone, V> Node<T, V> {
+impl<T: Clone, V, S: Summarize<V>> Node<T, V, S> {
     pub fn new<R: Into<Interval<T>>>(
         interval: R,
         value: V,
         max: T,
         height: usize,
         size: usize,
-    ) -> Node<T, V> {
+    ) -> Node<T, V, S> {
+        let summary = S::lift(&value);
         Node {
             interval: interval.into(),
             value: Some(value),
             max,
             height,
             size,
+            summary,
             left_child: None,
             right_child: None,
         }
@@ -47,30 +52,54 @@ impl<T: Ord + Clone, V> Node<T, V> {
         self.size = 1 + Node::size(&self.left_child) + Node::size(&self.right_child);
     }
 
-    pub fn update_max(&mut self) {
+    pub fn update_max<C: Comparator<T>>(&mut self, cmp: &C) {
+        let max_of = |a: &T, b: &T| -> T {
+            if cmp.compare(a, b) == Ordering::Greater {
+                a.clone()
+            } else {
+                b.clone()
+            }
+        };
+
         self.max = match (&self.left_child, &self.right_child) {
-            (Some(left_child), Some(right_child)) => max(
-                self.interval.end.clone(),
-                max(left_child.max.clone(), right_child.max.clone()),
+            (Some(left_child), Some(right_child)) => max_of(
+                &self.interval.end,
+                &max_of(&left_child.max, &right_child.max),
             ),
-            (Some(left_child), None) => max(self.interval.end.clone(), left_child.max.clone()),
-            (None, Some(right_child)) => max(self.interval.end.clone(), right_child.max.clone()),
+            (Some(left_child), None) => max_of(&self.interval.end, &left_child.max),
+            (None, Some(right_child)) => max_of(&self.interval.end, &right_child.max),
             (None, None) => self.interval.end.clone(),
         };
     }
 
-    pub fn max_height(node1: &Option<Box<Node<T, V>>>, node2: &Option<Box<Node<T, V>>>) -> i64 {
+    pub fn update_summary(&mut self) {
+        let left = match &self.left_child {
+            Some(left_child) => left_child.summary.clone(),
+            None => S::unit(),
+        };
+        let right = match &self.right_child {
+            Some(right_child) => right_child.summary.clone(),
+            None => S::unit(),
+        };
+        let mid = S::lift(self.value.as_ref().unwrap());
+        self.summary = S::combine(&S::combine(&left, &mid), &right);
+    }
+
+    pub fn max_height(
+        node1: &Option<Box<Node<T, V, S>>>,
+        node2: &Option<Box<Node<T, V, S>>>,
+    ) -> i64 {
         max(Node::height(node1), Node::height(node2))
     }
 
-    pub fn height(node: &Option<Box<Node<T, V>>>) -> i64 {
+    pub fn height(node: &Option<Box<Node<T, V, S>>>) -> i64 {
         match node {
             Some(node) => node.height as i64,
             None => -1,
         }
     }
 
-    pub fn size(node: &Option<Box<Node<T, V>>>) -> usize {
+    pub fn size(node: &Option<Box<Node<T, V, S>>>) -> usize {
         match node {
             Some(node) => node.size,
             None => 0,
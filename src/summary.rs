@@ -0,0 +1,24 @@
+use core::fmt::Debug;
+
+pub trait Summarize<V> {
+    type Summary: Clone + Debug;
+
+    fn unit() -> Self::Summary;
+
+    fn lift(value: &V) -> Self::Summary;
+
+    fn combine(a: &Self::Summary, b: &Self::Summary) -> Self::Summary;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoSummary;
+
+impl<V> Summarize<V> for NoSummary {
+    type Summary = ();
+
+    fn unit() -> Self::Summary {}
+
+    fn lift(_value: &V) -> Self::Summary {}
+
+    fn combine(_a: &Self::Summary, _b: &Self::Summary) -> Self::Summary {}
+}